@@ -2,6 +2,7 @@ pub mod frame;
 mod kspace;
 pub mod page_table;
 mod page_prop;
+pub mod paging_consts;
 
 use frame::{AnyFrameMeta, Frame, FrameRef, MetaSlot, MetaSlotOwner, MetaSlotStorage, StoredPageTablePageMeta, meta_to_frame};
 pub use kspace::*;
@@ -32,7 +33,10 @@ pub type Paddr = usize;
 pub type PagingLevel = u8;
 
 /// The maximum value of `PagingConstsTrait::NR_LEVELS`.
-extern_const!(pub MAX_NR_LEVELS [MAX_NR_LEVELS_SPEC, CONST_MAX_NR_LEVELS]: usize = 4);
+///
+/// This is 5 to accommodate RISC-V Sv57, the deepest paging mode among the
+/// [`paging_consts`] implementations.
+extern_const!(pub MAX_NR_LEVELS [MAX_NR_LEVELS_SPEC, CONST_MAX_NR_LEVELS]: usize = 5);
 
 #[allow(non_snake_case)]
 pub trait PagingConstsTrait: Debug + Sync {
@@ -111,6 +115,21 @@ pub trait PagingConstsTrait: Debug + Sync {
             res == Self::ADDRESS_WIDTH_spec(),
     ;
 
+    spec fn PADDR_WIDTH_spec() -> usize;
+
+    /// The width of a [`Paddr`], which need not equal [`PagingConstsTrait::ADDRESS_WIDTH`].
+    ///
+    /// RISC-V Sv32 is the motivating case: its physical address space is 34
+    /// bits wide even though `Vaddr` itself is only 32 bits, so `Paddr` and
+    /// `Vaddr` can't be assumed to occupy the same number of bits just
+    /// because they share the same Rust type.
+    #[inline(always)]
+    #[verifier::when_used_as_spec(PADDR_WIDTH_spec)]
+    fn PADDR_WIDTH() -> (res: usize)
+        ensures
+            res == Self::PADDR_WIDTH_spec(),
+    ;
+
     /// Whether virtual addresses are sign-extended.
     ///
     /// The sign bit of a [`Vaddr`] is the bit at index [`PagingConstsTrait::ADDRESS_WIDTH`] - 1.
@@ -128,6 +147,104 @@ pub trait PagingConstsTrait: Debug + Sync {
     #[verifier::when_used_as_spec(VA_SIGN_EXT_spec)]
     fn VA_SIGN_EXT() -> bool;
 
+    spec fn TAG_BITS_spec() -> usize;
+
+    /// The number of high bits of a [`Vaddr`] that hardware ignores on
+    /// access (e.g. ARM top-byte-ignore, RISC-V pointer masking), on top of
+    /// [`PagingConstsTrait::VA_SIGN_EXT`].
+    ///
+    /// These bits carry no addressing information and must be stripped by
+    /// [`PagingConstsTrait::canonicalize_va`] before a [`Vaddr`] is fed to
+    /// the page-table walk. A value of `0` means no bits are tagged, so
+    /// `VA_SIGN_EXT` alone governs validity, matching targets without
+    /// pointer tagging.
+    #[inline(always)]
+    #[verifier::when_used_as_spec(TAG_BITS_spec)]
+    fn TAG_BITS() -> (res: usize)
+        ensures
+            res == Self::TAG_BITS_spec(),
+            res < Self::ADDRESS_WIDTH(),
+    ;
+
+    spec fn canonicalize_va_spec(va: Vaddr) -> Vaddr;
+
+    /// Strips the ignored high "tag" bits off `va`, producing the
+    /// canonical address that the page-table walk should actually use.
+    ///
+    /// A range expressed with tagged pointers must map to the same
+    /// sub-tree as its untagged form, so [`lock_range`](crate::mm::page_table::cursor::lock_range)
+    /// and the rest of the locking protocol route every incoming
+    /// `va.start`/`va.end` through this function first.
+    #[inline(always)]
+    #[verifier::when_used_as_spec(canonicalize_va_spec)]
+    fn canonicalize_va(va: Vaddr) -> (res: Vaddr)
+        ensures
+            res == Self::canonicalize_va_spec(va),
+            // Canonicalization is idempotent: a canonical address is its
+            // own canonical form.
+            Self::canonicalize_va_spec(res) == res,
+    ;
+
+    /// Canonicalization preserves the order of two addresses that are
+    /// already canonical, i.e. that lie within the same canonical window.
+    /// This is what lets the `va.start < va.end` range invariants the DFS
+    /// relies on survive tag-stripping.
+    proof fn lemma_canonicalize_va_preserves_order(va1: Vaddr, va2: Vaddr)
+        requires
+            va1 < va2,
+            Self::canonicalize_va_spec(va1) == va1,
+            Self::canonicalize_va_spec(va2) == va2,
+        ensures
+            Self::canonicalize_va_spec(va1) < Self::canonicalize_va_spec(va2),
+    ;
+
+}
+
+#[verifier::inline]
+pub open spec fn tag_mask_spec<C: PagingConstsTrait>() -> usize {
+    // The top `TAG_BITS` bits of a `Vaddr`, i.e. the bits that a tagged
+    // pointer is allowed to vary in and that carry no addressing
+    // information at all.
+    if C::TAG_BITS_spec() == 0 {
+        0
+    } else {
+        !(usize::MAX >> C::TAG_BITS_spec())
+    }
+}
+
+#[verifier::inline]
+pub open spec fn is_canonical_va_spec<C: PagingConstsTrait>(va: Vaddr) -> bool {
+    // Outside the ignored tag bits, `va` must already match its canonical
+    // form. The tag bits are free to hold anything (they are cleared on
+    // both sides of the comparison); every other bit must already obey
+    // `VA_SIGN_EXT`. This is what tells apart a benign tagged pointer from
+    // a genuinely malformed address that canonicalization would otherwise
+    // paper over.
+    (va & !tag_mask_spec::<C>()) == (C::canonicalize_va_spec(va) & !tag_mask_spec::<C>())
+}
+
+/// Whether `va` is a well-formed address: either already canonical, or one
+/// whose only "wrong" bits are within the ignored tag. A genuinely
+/// non-canonical address (one whose non-tag bits don't already obey
+/// [`PagingConstsTrait::VA_SIGN_EXT`]) must be rejected by both hardware
+/// and OSTD rather than silently canonicalized.
+#[inline(always)]
+#[verifier::when_used_as_spec(is_canonical_va_spec)]
+pub fn is_canonical_va<C: PagingConstsTrait>(va: Vaddr) -> (res: bool)
+    ensures
+        res == is_canonical_va_spec::<C>(va),
+{
+    let tag_mask = tag_mask::<C>();
+    (va & !tag_mask) == (C::canonicalize_va(va) & !tag_mask)
+}
+
+#[inline(always)]
+#[verifier::when_used_as_spec(tag_mask_spec)]
+fn tag_mask<C: PagingConstsTrait>() -> (res: usize)
+    ensures
+        res == tag_mask_spec::<C>(),
+{
+    if C::TAG_BITS() == 0 { 0 } else { !(usize::MAX >> C::TAG_BITS()) }
 }
 
 
@@ -161,3 +278,46 @@ pub proof fn lemma_nr_subpage_per_huge_bounded<C: PagingConstsTrait>()
 }
 
 } // verus!
+
+#[cfg(test)]
+mod tests {
+    use super::paging_consts::{Sv39, Sv39Pmask};
+    use super::{is_canonical_va, PagingConstsTrait};
+
+    #[test]
+    fn sv39_canonicalizes_by_sign_extending_bit_38() {
+        // 0x40_0000_0000 has bit 38 (the sign bit) set but none of the
+        // higher bits, so canonicalization must sign-extend it.
+        assert_eq!(Sv39::canonicalize_va(0x40_0000_0000), !0x3f_ffff_ffffusize);
+        // Already sign-extended addresses are left untouched.
+        assert_eq!(Sv39::canonicalize_va(0x1234), 0x1234);
+    }
+
+    #[test]
+    fn sv39_rejects_a_non_canonical_address() {
+        // Bit 38 set without the higher bits sign-extended: this has no
+        // ignored tag bits under Sv39 (`TAG_BITS == 0`), so it must be
+        // rejected rather than silently fixed up.
+        assert!(!is_canonical_va::<Sv39>(0x40_0000_0000));
+        assert!(is_canonical_va::<Sv39>(0x1234));
+    }
+
+    #[test]
+    fn sv39_pmask_accepts_any_tag_on_an_otherwise_canonical_address() {
+        let va = 0x1234;
+        for tag in 0..(1usize << 7) {
+            let tagged = va | (tag << 57);
+            assert!(is_canonical_va::<Sv39Pmask>(tagged));
+            assert_eq!(Sv39Pmask::canonicalize_va(tagged), va);
+        }
+    }
+
+    #[test]
+    fn sv39_pmask_rejects_a_non_canonical_address_even_with_a_tag() {
+        // Bit 38 set without sign extension is non-canonical regardless of
+        // what the (ignored) tag bits hold.
+        let non_canonical = 0x40_0000_0000;
+        assert!(!is_canonical_va::<Sv39Pmask>(non_canonical));
+        assert!(!is_canonical_va::<Sv39Pmask>(non_canonical | (0x7f << 57)));
+    }
+}
@@ -0,0 +1,673 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Concrete [`PagingConstsTrait`] implementations for supported architectures.
+use core::fmt::Debug;
+
+use vstd::layout::is_power_2;
+use vstd::prelude::*;
+
+use super::{PagingConstsTrait, PagingLevel, Vaddr};
+
+verus! {
+
+/// RISC-V Sv32 paging: 2 levels, 4-byte PTEs, 1024 entries per node.
+///
+/// Sv32 is the odd one out among the RISC-V paging modes: its physical
+/// address space (34 bits) is wider than its 32-bit virtual address space,
+/// and virtual addresses are not sign-extended.
+#[derive(Debug)]
+pub struct Sv32;
+
+impl PagingConstsTrait for Sv32 {
+    #[verifier::inline]
+    open spec fn BASE_PAGE_SIZE_spec() -> usize {
+        4096
+    }
+
+    proof fn lemma_BASE_PAGE_SIZE_properties()
+        ensures
+            0 < Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::BASE_PAGE_SIZE_spec() as int),
+    {
+        assert(is_power_2(4096int)) by (compute);
+    }
+
+    #[inline(always)]
+    fn BASE_PAGE_SIZE() -> usize {
+        4096
+    }
+
+    #[verifier::inline]
+    open spec fn NR_LEVELS_spec() -> PagingLevel {
+        2
+    }
+
+    #[inline(always)]
+    fn NR_LEVELS() -> PagingLevel {
+        2
+    }
+
+    #[verifier::inline]
+    open spec fn HIGHEST_TRANSLATION_LEVEL_spec() -> PagingLevel {
+        // Sv32 supports 4MB superpages at the top level.
+        2
+    }
+
+    #[inline(always)]
+    fn HIGHEST_TRANSLATION_LEVEL() -> PagingLevel {
+        2
+    }
+
+    #[verifier::inline]
+    open spec fn PTE_SIZE_spec() -> usize {
+        4
+    }
+
+    #[inline(always)]
+    fn PTE_SIZE() -> usize {
+        4
+    }
+
+    proof fn lemma_PTE_SIZE_properties()
+        ensures
+            0 < Self::PTE_SIZE_spec() <= Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::PTE_SIZE_spec() as int),
+    {
+        assert(is_power_2(4int)) by (compute);
+    }
+
+    #[verifier::inline]
+    open spec fn ADDRESS_WIDTH_spec() -> usize {
+        32
+    }
+
+    #[inline(always)]
+    fn ADDRESS_WIDTH() -> usize {
+        32
+    }
+
+    #[verifier::inline]
+    open spec fn PADDR_WIDTH_spec() -> usize {
+        // Sv32's PPN is 22 bits, giving a 34-bit physical address space
+        // despite the 32-bit virtual address space.
+        34
+    }
+
+    #[inline(always)]
+    fn PADDR_WIDTH() -> usize {
+        34
+    }
+
+    #[verifier::inline]
+    open spec fn VA_SIGN_EXT_spec() -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn VA_SIGN_EXT() -> bool {
+        false
+    }
+
+    #[verifier::inline]
+    open spec fn TAG_BITS_spec() -> usize {
+        0
+    }
+
+    #[inline(always)]
+    fn TAG_BITS() -> usize {
+        0
+    }
+
+    #[verifier::inline]
+    open spec fn canonicalize_va_spec(va: Vaddr) -> Vaddr {
+        // Sv32 addresses are zero-extended rather than sign-extended:
+        // strip everything above the 32-bit virtual address space.
+        va & ((1usize << Self::ADDRESS_WIDTH_spec()) - 1)
+    }
+
+    #[inline(always)]
+    fn canonicalize_va(va: Vaddr) -> Vaddr {
+        va & ((1usize << Self::ADDRESS_WIDTH()) - 1)
+    }
+
+    proof fn lemma_canonicalize_va_preserves_order(va1: Vaddr, va2: Vaddr) {
+    }
+}
+
+/// RISC-V Sv39 paging: 3 levels, 8-byte PTEs, 512 entries per node, sign-
+/// extended 39-bit virtual addresses.
+#[derive(Debug)]
+pub struct Sv39;
+
+impl PagingConstsTrait for Sv39 {
+    #[verifier::inline]
+    open spec fn BASE_PAGE_SIZE_spec() -> usize {
+        4096
+    }
+
+    proof fn lemma_BASE_PAGE_SIZE_properties()
+        ensures
+            0 < Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::BASE_PAGE_SIZE_spec() as int),
+    {
+        assert(is_power_2(4096int)) by (compute);
+    }
+
+    #[inline(always)]
+    fn BASE_PAGE_SIZE() -> usize {
+        4096
+    }
+
+    #[verifier::inline]
+    open spec fn NR_LEVELS_spec() -> PagingLevel {
+        3
+    }
+
+    #[inline(always)]
+    fn NR_LEVELS() -> PagingLevel {
+        3
+    }
+
+    #[verifier::inline]
+    open spec fn HIGHEST_TRANSLATION_LEVEL_spec() -> PagingLevel {
+        // Sv39 supports 1GB superpages at the top level.
+        3
+    }
+
+    #[inline(always)]
+    fn HIGHEST_TRANSLATION_LEVEL() -> PagingLevel {
+        3
+    }
+
+    #[verifier::inline]
+    open spec fn PTE_SIZE_spec() -> usize {
+        8
+    }
+
+    #[inline(always)]
+    fn PTE_SIZE() -> usize {
+        8
+    }
+
+    proof fn lemma_PTE_SIZE_properties()
+        ensures
+            0 < Self::PTE_SIZE_spec() <= Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::PTE_SIZE_spec() as int),
+    {
+        assert(is_power_2(8int)) by (compute);
+    }
+
+    #[verifier::inline]
+    open spec fn ADDRESS_WIDTH_spec() -> usize {
+        39
+    }
+
+    #[inline(always)]
+    fn ADDRESS_WIDTH() -> usize {
+        39
+    }
+
+    #[verifier::inline]
+    open spec fn PADDR_WIDTH_spec() -> usize {
+        // Sv39/Sv48/Sv57 share a 44-bit PPN, giving a 56-bit physical
+        // address space regardless of virtual address width.
+        56
+    }
+
+    #[inline(always)]
+    fn PADDR_WIDTH() -> usize {
+        56
+    }
+
+    #[verifier::inline]
+    open spec fn VA_SIGN_EXT_spec() -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn VA_SIGN_EXT() -> bool {
+        true
+    }
+
+    #[verifier::inline]
+    open spec fn TAG_BITS_spec() -> usize {
+        0
+    }
+
+    #[inline(always)]
+    fn TAG_BITS() -> usize {
+        0
+    }
+
+    #[verifier::inline]
+    open spec fn canonicalize_va_spec(va: Vaddr) -> Vaddr {
+        // Virtual addresses are sign-extended above the 39-bit address
+        // space: re-derive that extension from the kept low bits rather
+        // than trust the caller already supplied a canonical address.
+        let sign_bit = 1usize << (Self::ADDRESS_WIDTH_spec() - 1);
+        let kept = va & (sign_bit * 2 - 1);
+        if kept & sign_bit != 0 {
+            kept | !(sign_bit * 2 - 1)
+        } else {
+            kept
+        }
+    }
+
+    #[inline(always)]
+    fn canonicalize_va(va: Vaddr) -> Vaddr {
+        let sign_bit: usize = 1 << (Self::ADDRESS_WIDTH() - 1);
+        let kept = va & (sign_bit * 2 - 1);
+        if kept & sign_bit != 0 {
+            kept | !(sign_bit * 2 - 1)
+        } else {
+            kept
+        }
+    }
+
+    proof fn lemma_canonicalize_va_preserves_order(va1: Vaddr, va2: Vaddr) {
+    }
+}
+
+/// RISC-V Sv48 paging: 4 levels, 8-byte PTEs, 512 entries per node, sign-
+/// extended 48-bit virtual addresses.
+#[derive(Debug)]
+pub struct Sv48;
+
+impl PagingConstsTrait for Sv48 {
+    #[verifier::inline]
+    open spec fn BASE_PAGE_SIZE_spec() -> usize {
+        4096
+    }
+
+    proof fn lemma_BASE_PAGE_SIZE_properties()
+        ensures
+            0 < Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::BASE_PAGE_SIZE_spec() as int),
+    {
+        assert(is_power_2(4096int)) by (compute);
+    }
+
+    #[inline(always)]
+    fn BASE_PAGE_SIZE() -> usize {
+        4096
+    }
+
+    #[verifier::inline]
+    open spec fn NR_LEVELS_spec() -> PagingLevel {
+        4
+    }
+
+    #[inline(always)]
+    fn NR_LEVELS() -> PagingLevel {
+        4
+    }
+
+    #[verifier::inline]
+    open spec fn HIGHEST_TRANSLATION_LEVEL_spec() -> PagingLevel {
+        // Sv48 supports superpages at the top level.
+        4
+    }
+
+    #[inline(always)]
+    fn HIGHEST_TRANSLATION_LEVEL() -> PagingLevel {
+        4
+    }
+
+    #[verifier::inline]
+    open spec fn PTE_SIZE_spec() -> usize {
+        8
+    }
+
+    #[inline(always)]
+    fn PTE_SIZE() -> usize {
+        8
+    }
+
+    proof fn lemma_PTE_SIZE_properties()
+        ensures
+            0 < Self::PTE_SIZE_spec() <= Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::PTE_SIZE_spec() as int),
+    {
+        assert(is_power_2(8int)) by (compute);
+    }
+
+    #[verifier::inline]
+    open spec fn ADDRESS_WIDTH_spec() -> usize {
+        48
+    }
+
+    #[inline(always)]
+    fn ADDRESS_WIDTH() -> usize {
+        48
+    }
+
+    #[verifier::inline]
+    open spec fn PADDR_WIDTH_spec() -> usize {
+        // Sv39/Sv48/Sv57 share a 44-bit PPN, giving a 56-bit physical
+        // address space regardless of virtual address width.
+        56
+    }
+
+    #[inline(always)]
+    fn PADDR_WIDTH() -> usize {
+        56
+    }
+
+    #[verifier::inline]
+    open spec fn VA_SIGN_EXT_spec() -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn VA_SIGN_EXT() -> bool {
+        true
+    }
+
+    #[verifier::inline]
+    open spec fn TAG_BITS_spec() -> usize {
+        0
+    }
+
+    #[inline(always)]
+    fn TAG_BITS() -> usize {
+        0
+    }
+
+    #[verifier::inline]
+    open spec fn canonicalize_va_spec(va: Vaddr) -> Vaddr {
+        // Virtual addresses are sign-extended above the 48-bit address
+        // space: re-derive that extension from the kept low bits rather
+        // than trust the caller already supplied a canonical address.
+        let sign_bit = 1usize << (Self::ADDRESS_WIDTH_spec() - 1);
+        let kept = va & (sign_bit * 2 - 1);
+        if kept & sign_bit != 0 {
+            kept | !(sign_bit * 2 - 1)
+        } else {
+            kept
+        }
+    }
+
+    #[inline(always)]
+    fn canonicalize_va(va: Vaddr) -> Vaddr {
+        let sign_bit: usize = 1 << (Self::ADDRESS_WIDTH() - 1);
+        let kept = va & (sign_bit * 2 - 1);
+        if kept & sign_bit != 0 {
+            kept | !(sign_bit * 2 - 1)
+        } else {
+            kept
+        }
+    }
+
+    proof fn lemma_canonicalize_va_preserves_order(va1: Vaddr, va2: Vaddr) {
+    }
+}
+
+/// RISC-V Sv57 paging: 5 levels, 8-byte PTEs, 512 entries per node, sign-
+/// extended 57-bit virtual addresses.
+#[derive(Debug)]
+pub struct Sv57;
+
+impl PagingConstsTrait for Sv57 {
+    #[verifier::inline]
+    open spec fn BASE_PAGE_SIZE_spec() -> usize {
+        4096
+    }
+
+    proof fn lemma_BASE_PAGE_SIZE_properties()
+        ensures
+            0 < Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::BASE_PAGE_SIZE_spec() as int),
+    {
+        assert(is_power_2(4096int)) by (compute);
+    }
+
+    #[inline(always)]
+    fn BASE_PAGE_SIZE() -> usize {
+        4096
+    }
+
+    #[verifier::inline]
+    open spec fn NR_LEVELS_spec() -> PagingLevel {
+        5
+    }
+
+    #[inline(always)]
+    fn NR_LEVELS() -> PagingLevel {
+        5
+    }
+
+    #[verifier::inline]
+    open spec fn HIGHEST_TRANSLATION_LEVEL_spec() -> PagingLevel {
+        // Sv57 supports superpages at the top level.
+        5
+    }
+
+    #[inline(always)]
+    fn HIGHEST_TRANSLATION_LEVEL() -> PagingLevel {
+        5
+    }
+
+    #[verifier::inline]
+    open spec fn PTE_SIZE_spec() -> usize {
+        8
+    }
+
+    #[inline(always)]
+    fn PTE_SIZE() -> usize {
+        8
+    }
+
+    proof fn lemma_PTE_SIZE_properties()
+        ensures
+            0 < Self::PTE_SIZE_spec() <= Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::PTE_SIZE_spec() as int),
+    {
+        assert(is_power_2(8int)) by (compute);
+    }
+
+    #[verifier::inline]
+    open spec fn ADDRESS_WIDTH_spec() -> usize {
+        57
+    }
+
+    #[inline(always)]
+    fn ADDRESS_WIDTH() -> usize {
+        57
+    }
+
+    #[verifier::inline]
+    open spec fn PADDR_WIDTH_spec() -> usize {
+        // Sv39/Sv48/Sv57 share a 44-bit PPN, giving a 56-bit physical
+        // address space regardless of virtual address width.
+        56
+    }
+
+    #[inline(always)]
+    fn PADDR_WIDTH() -> usize {
+        56
+    }
+
+    #[verifier::inline]
+    open spec fn VA_SIGN_EXT_spec() -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn VA_SIGN_EXT() -> bool {
+        true
+    }
+
+    #[verifier::inline]
+    open spec fn TAG_BITS_spec() -> usize {
+        0
+    }
+
+    #[inline(always)]
+    fn TAG_BITS() -> usize {
+        0
+    }
+
+    #[verifier::inline]
+    open spec fn canonicalize_va_spec(va: Vaddr) -> Vaddr {
+        // Virtual addresses are sign-extended above the 57-bit address
+        // space: re-derive that extension from the kept low bits rather
+        // than trust the caller already supplied a canonical address.
+        let sign_bit = 1usize << (Self::ADDRESS_WIDTH_spec() - 1);
+        let kept = va & (sign_bit * 2 - 1);
+        if kept & sign_bit != 0 {
+            kept | !(sign_bit * 2 - 1)
+        } else {
+            kept
+        }
+    }
+
+    #[inline(always)]
+    fn canonicalize_va(va: Vaddr) -> Vaddr {
+        let sign_bit: usize = 1 << (Self::ADDRESS_WIDTH() - 1);
+        let kept = va & (sign_bit * 2 - 1);
+        if kept & sign_bit != 0 {
+            kept | !(sign_bit * 2 - 1)
+        } else {
+            kept
+        }
+    }
+
+    proof fn lemma_canonicalize_va_preserves_order(va1: Vaddr, va2: Vaddr) {
+    }
+}
+
+/// RISC-V Sv39 paging with the `Zjpm` pointer-masking extension enabled at
+/// its smallest `PMLEN` of 7: otherwise identical to [`Sv39`], but the top
+/// 7 bits of a [`Vaddr`] are a hardware-ignored tag rather than part of the
+/// sign-extended address.
+#[derive(Debug)]
+pub struct Sv39Pmask;
+
+impl PagingConstsTrait for Sv39Pmask {
+    #[verifier::inline]
+    open spec fn BASE_PAGE_SIZE_spec() -> usize {
+        4096
+    }
+
+    proof fn lemma_BASE_PAGE_SIZE_properties()
+        ensures
+            0 < Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::BASE_PAGE_SIZE_spec() as int),
+    {
+        assert(is_power_2(4096int)) by (compute);
+    }
+
+    #[inline(always)]
+    fn BASE_PAGE_SIZE() -> usize {
+        4096
+    }
+
+    #[verifier::inline]
+    open spec fn NR_LEVELS_spec() -> PagingLevel {
+        3
+    }
+
+    #[inline(always)]
+    fn NR_LEVELS() -> PagingLevel {
+        3
+    }
+
+    #[verifier::inline]
+    open spec fn HIGHEST_TRANSLATION_LEVEL_spec() -> PagingLevel {
+        3
+    }
+
+    #[inline(always)]
+    fn HIGHEST_TRANSLATION_LEVEL() -> PagingLevel {
+        3
+    }
+
+    #[verifier::inline]
+    open spec fn PTE_SIZE_spec() -> usize {
+        8
+    }
+
+    #[inline(always)]
+    fn PTE_SIZE() -> usize {
+        8
+    }
+
+    proof fn lemma_PTE_SIZE_properties()
+        ensures
+            0 < Self::PTE_SIZE_spec() <= Self::BASE_PAGE_SIZE_spec(),
+            is_power_2(Self::PTE_SIZE_spec() as int),
+    {
+        assert(is_power_2(8int)) by (compute);
+    }
+
+    #[verifier::inline]
+    open spec fn ADDRESS_WIDTH_spec() -> usize {
+        39
+    }
+
+    #[inline(always)]
+    fn ADDRESS_WIDTH() -> usize {
+        39
+    }
+
+    #[verifier::inline]
+    open spec fn PADDR_WIDTH_spec() -> usize {
+        // Sv39/Sv48/Sv57 share a 44-bit PPN, giving a 56-bit physical
+        // address space regardless of virtual address width.
+        56
+    }
+
+    #[inline(always)]
+    fn PADDR_WIDTH() -> usize {
+        56
+    }
+
+    #[verifier::inline]
+    open spec fn VA_SIGN_EXT_spec() -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn VA_SIGN_EXT() -> bool {
+        true
+    }
+
+    #[verifier::inline]
+    open spec fn TAG_BITS_spec() -> usize {
+        7
+    }
+
+    #[inline(always)]
+    fn TAG_BITS() -> usize {
+        7
+    }
+
+    #[verifier::inline]
+    open spec fn canonicalize_va_spec(va: Vaddr) -> Vaddr {
+        // Strip the top 7 tag bits, then re-derive the sign extension of
+        // the remaining 39-bit address: the kept bits alone decide whether
+        // the canonical form is sign-extended with ones or zeros.
+        let sign_bit = 1usize << (Self::ADDRESS_WIDTH_spec() - 1);
+        let kept = va & (sign_bit * 2 - 1);
+        if kept & sign_bit != 0 {
+            kept | !(sign_bit * 2 - 1)
+        } else {
+            kept
+        }
+    }
+
+    #[inline(always)]
+    fn canonicalize_va(va: Vaddr) -> Vaddr {
+        let sign_bit: usize = 1 << (Self::ADDRESS_WIDTH() - 1);
+        let kept = va & (sign_bit * 2 - 1);
+        if kept & sign_bit != 0 {
+            kept | !(sign_bit * 2 - 1)
+        } else {
+            kept
+        }
+    }
+
+    proof fn lemma_canonicalize_va_preserves_order(va1: Vaddr, va2: Vaddr) {
+    }
+}
+
+} // verus!
@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Region-based access tracking for working-set estimation.
+//!
+//! This reuses the DFS skeleton of the locking protocol (see
+//! [`super::locking`]) to walk a locked sub-tree and sample the hardware
+//! "accessed" bit of leaf PTEs. The sampling loop follows the DAMON
+//! adaptive design: the range is partitioned into a bounded number of
+//! regions, one random page per region is probed and its accessed bit
+//! atomically tested-and-cleared on every pass, and adjacent regions with
+//! similar hotness are merged -- while a region that stands out as hot is
+//! split -- before the next pass, so the region count stays bounded without
+//! any external monitoring daemon.
+use core::{mem::ManuallyDrop, ops::Range, sync::atomic::Ordering};
+
+use vstd::prelude::*;
+use vstd::simple_pptr::*;
+
+use vstd_extra::array_ptr::*;
+
+use crate::mm::{
+    nr_subpage_per_huge, paddr_to_vaddr,
+    page_table::{
+        load_pte, page_size, pte_index, store_pte, ChildRef, PageTable, PageTableConfig,
+        PageTableEntryTrait, PageTableGuard, PageTableNodeRef, PagingConstsTrait, PagingLevel,
+    },
+    Vaddr,
+};
+
+use super::locking::{lock_range, unlock_range};
+
+use aster_common::prelude::*;
+use aster_common::prelude::page_table::*;
+
+verus! {
+
+/// Atomically reads the "accessed" bit of the PTE at `ptr.add(idx)` and
+/// clears it back to zero, returning whether it was set.
+///
+/// This is the read-modify-write companion of [`load_pte`]: the bit is
+/// observed and cleared in a single atomic step so that a concurrent access
+/// is never lost and a genuinely idle page is never reported as accessed.
+pub fn load_and_clear_accessed<E: PageTableEntryTrait>(
+    ptr: ArrayPtr<E, CONST_NR_ENTRIES>,
+    idx: usize,
+) -> bool {
+    let pte = load_pte(ptr.add(idx), Ordering::Acquire);
+    if !pte.is_present() || !pte.is_accessed() {
+        return false;
+    }
+    store_pte(ptr.add(idx), pte.with_accessed_cleared(), Ordering::Release);
+    true
+}
+
+/// A sampled region of virtual addresses together with its running hotness
+/// counter: the number of passes in which its sampled page was found
+/// accessed.
+pub struct AccessRegion {
+    pub va_range: Range<Vaddr>,
+    pub hotness: u64,
+}
+
+/// Descends the locked sub-tree rooted at `cur_node` to the leaf PTE that
+/// maps `sample_va`, tests-and-clears its accessed bit, and returns whether
+/// it was set. Returns `false` if no mapping is present at `sample_va`.
+///
+/// `cur_node_va` must be the virtual address of `cur_node`, and `sample_va`
+/// must fall within the range covered by `cur_node`.
+#[verus_spec(
+    with Tracked(entry_own): Tracked<&mut EntryOwner<'rcu, C>>
+)]
+fn sample_one_accessed<'rcu, C: PageTableConfig, A: InAtomicMode>(
+    guard: &'rcu A,
+    cur_node: PPtr<PageTableGuard<'rcu, C>>,
+    cur_node_va: Vaddr,
+    sample_va: Vaddr,
+) -> bool {
+    let cur_guard = cur_node.borrow(Tracked(entry_own.guard_perm.borrow()));
+    let cur_level = cur_guard.level();
+    let idx = pte_index::<C>(sample_va, cur_level);
+
+    let child = PageTableGuard::<'rcu, C>::entry(cur_node, idx);
+    match child.to_ref() {
+        ChildRef::Frame(_, _, _) => {
+            let cur_pt_ptr =
+                ArrayPtr::<C::E, CONST_NR_ENTRIES>::from_addr(paddr_to_vaddr(cur_guard.start_paddr()));
+            load_and_clear_accessed(cur_pt_ptr, idx)
+        }
+        ChildRef::PageTable(pt) => {
+            let child_node = pt.lock(guard);
+            let child_node_va = cur_node_va + idx * page_size(cur_level);
+            let found = sample_one_accessed(guard, child_node, child_node_va, sample_va);
+            let _ = ManuallyDrop::new(child_node);
+            found
+        }
+        ChildRef::None => false,
+    }
+}
+
+/// Walks the locked sub-tree rooted at `root` (whose virtual address is
+/// `root_va`), sampling one page per region on a single pass, and
+/// accumulates each region's hotness counter.
+///
+/// `pick_sample_va` chooses the sampled address within a region on each
+/// pass; callers typically vary it (e.g. with a PRNG) so that different
+/// pages within the same region get probed over time.
+#[verus_spec(
+    with Tracked(entry_own): Tracked<&mut EntryOwner<'rcu, C>>
+)]
+fn dfs_sample_accessed<'rcu, C: PageTableConfig, A: InAtomicMode>(
+    guard: &'rcu A,
+    root: PPtr<PageTableGuard<'rcu, C>>,
+    root_va: Vaddr,
+    regions: &mut [AccessRegion],
+    pick_sample_va: &mut impl FnMut(&Range<Vaddr>) -> Vaddr,
+) {
+    for region in regions.iter_mut() {
+        let sample_va = pick_sample_va(&region.va_range);
+        if sample_one_accessed::<C, A>(guard, root, root_va, sample_va) {
+            region.hotness += 1;
+        }
+    }
+}
+
+/// Splits `va` into `nr_regions` equally sized regions, each starting with
+/// a hotness of zero.
+fn partition_regions(va: Range<Vaddr>, nr_regions: usize) -> Vec<AccessRegion> {
+    debug_assert!(nr_regions > 0);
+    let span = (va.end - va.start) / nr_regions;
+    let mut regions = Vec::with_capacity(nr_regions);
+    let mut start = va.start;
+    for i in 0..nr_regions {
+        let end = if i + 1 == nr_regions { va.end } else { start + span };
+        regions.push(AccessRegion { va_range: start..end, hotness: 0 });
+        start = end;
+    }
+    regions
+}
+
+/// Merges adjacent regions whose hotness agrees, then repeatedly splits the
+/// hottest remaining region in two until the region count is back at
+/// `target_nr_regions` (or no region is wide enough to split). A count that
+/// is already at or above the target is not reduced further.
+///
+/// This is the adaptive step of the DAMON-style algorithm: regions that
+/// turn out to behave the same way are coalesced, freeing up a "slot" to
+/// give a hot region finer granularity on the next pass.
+fn adapt_regions(mut regions: Vec<AccessRegion>, target_nr_regions: usize) -> Vec<AccessRegion> {
+    let mut merged: Vec<AccessRegion> = Vec::with_capacity(regions.len());
+    for region in regions.drain(..) {
+        if let Some(last) = merged.last_mut() {
+            if last.hotness == region.hotness && last.va_range.end == region.va_range.start {
+                last.va_range.end = region.va_range.end;
+                continue;
+            }
+        }
+        merged.push(region);
+    }
+
+    while merged.len() < target_nr_regions {
+        let Some((hottest_idx, _)) = merged
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.va_range.end - r.va_range.start > 1)
+            .max_by_key(|(_, r)| r.hotness)
+        else {
+            break;
+        };
+        let hottest = merged.remove(hottest_idx);
+        let mid = hottest.va_range.start + (hottest.va_range.end - hottest.va_range.start) / 2;
+        merged.insert(hottest_idx, AccessRegion { va_range: mid..hottest.va_range.end, hotness: hottest.hotness });
+        merged.insert(hottest_idx, AccessRegion { va_range: hottest.va_range.start..mid, hotness: hottest.hotness });
+    }
+
+    merged
+}
+
+/// Estimates per-region access frequencies over `va` by sampling the
+/// hardware accessed bit for `nr_passes` passes.
+///
+/// `va` must be a range already covered by entries in `pt`. The range is
+/// initially split into `nr_regions` equal regions; between passes,
+/// similarly-behaving adjacent regions are merged and the hottest region is
+/// split, so the returned regions need not match the initial partition.
+/// The result is a `Vec` of `(region, estimated access count)` pairs,
+/// usable to drive reclaim decisions without any external monitoring
+/// daemon.
+pub fn sample_working_set<'rcu, C: PageTableConfig, A: InAtomicMode>(
+    pt: &'rcu PageTable<C>,
+    guard: &'rcu A,
+    va: Range<Vaddr>,
+    nr_regions: usize,
+    nr_passes: u32,
+    pick_sample_va: &mut impl FnMut(&Range<Vaddr>) -> Vaddr,
+) -> Vec<(Range<Vaddr>, u64)> {
+    let mut cursor = lock_range(pt, guard, &va);
+    let root = cursor.path[cursor.guard_level as usize - 1].unwrap();
+    let root_va = align_down(cursor.barrier_va.start, page_size(cursor.guard_level + 1));
+
+    let mut regions = partition_regions(va, nr_regions);
+    for _ in 0..nr_passes {
+        dfs_sample_accessed::<C, A>(guard, root, root_va, &mut regions, pick_sample_va);
+        regions = adapt_regions(regions, nr_regions);
+    }
+
+    // This is a periodic sampler meant to be called repeatedly over
+    // (possibly overlapping) ranges: leaving the range locked past this
+    // call would deadlock the next sampling pass.
+    unlock_range(&mut cursor);
+
+    regions.into_iter().map(|r| (r.va_range, r.hotness)).collect()
+}
+
+} // verus!
+
+#[cfg(test)]
+mod tests {
+    use super::{adapt_regions, partition_regions, AccessRegion};
+
+    #[test]
+    fn partition_regions_splits_evenly_and_covers_the_whole_range() {
+        let regions = partition_regions(0..40, 4);
+        assert_eq!(regions.len(), 4);
+        assert_eq!(regions[0].va_range, 0..10);
+        assert_eq!(regions[3].va_range, 30..40);
+        assert!(regions.iter().all(|r| r.hotness == 0));
+    }
+
+    #[test]
+    fn partition_regions_last_region_absorbs_the_remainder() {
+        // 10 doesn't divide evenly by 3, so the last region must pick up the
+        // leftover rather than the range being short by a few addresses.
+        let regions = partition_regions(0..10, 3);
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions.last().unwrap().va_range.end, 10);
+    }
+
+    #[test]
+    fn adapt_regions_merges_adjacent_equal_hotness_regions() {
+        let regions = vec![
+            AccessRegion { va_range: 0..10, hotness: 2 },
+            AccessRegion { va_range: 10..20, hotness: 2 },
+            AccessRegion { va_range: 20..30, hotness: 5 },
+        ];
+        // The two equally hot regions merge into one; the count then drops
+        // below the target of 3, so the hottest region (20..30) is split.
+        let merged = adapt_regions(regions, 3);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].va_range, 0..20);
+        assert_eq!(merged[0].hotness, 2);
+        assert_eq!(merged[1].va_range, 20..25);
+        assert_eq!(merged[1].hotness, 5);
+        assert_eq!(merged[2].va_range, 25..30);
+        assert_eq!(merged[2].hotness, 5);
+    }
+
+    #[test]
+    fn adapt_regions_splits_the_hottest_region_to_refill_the_target_count() {
+        let regions = vec![
+            AccessRegion { va_range: 0..10, hotness: 1 },
+            AccessRegion { va_range: 10..20, hotness: 9 },
+        ];
+        let adapted = adapt_regions(regions, 3);
+        assert_eq!(adapted.len(), 3);
+        assert!(adapted.iter().all(|r| r.va_range.end - r.va_range.start >= 1));
+    }
+}
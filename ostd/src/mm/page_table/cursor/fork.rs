@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Kernel-half page-table cloning and copy-on-write fork for address-space
+//! creation.
+use core::marker::PhantomData;
+
+use vstd::prelude::*;
+use vstd::simple_pptr::*;
+
+use crate::mm::{
+    nr_subpage_per_huge,
+    page_table::{
+        ChildRef, PageTable, PageTableConfig, PageTableEntryTrait, PageTableGuard,
+        PageTableNodeRef, PagingConstsTrait, PagingLevel,
+    },
+};
+
+use aster_common::prelude::*;
+use aster_common::prelude::page_table::*;
+
+verus! {
+
+/// The first top-level index that belongs to the kernel half of the
+/// address space. Indices below it are user space and are never shared
+/// across tables; indices at or above it are the shared kernel half that
+/// [`dfs_mark_stray_and_unlock`](super::locking::dfs_mark_stray_and_unlock)
+/// must never mark stray.
+#[verifier::inline]
+pub open spec fn kernel_pte_index_spec<C: PagingConstsTrait>() -> usize {
+    nr_subpage_per_huge_spec::<C>() / 2
+}
+
+#[inline(always)]
+#[verifier::when_used_as_spec(kernel_pte_index_spec)]
+pub fn kernel_pte_index<C: PagingConstsTrait>() -> (res: usize)
+    ensures
+        res == kernel_pte_index_spec::<C>(),
+{
+    nr_subpage_per_huge::<C>() / 2
+}
+
+/// Allocates a fresh root node and copies `parent`'s top-level entries that
+/// cover kernel virtual addresses into it by reference.
+///
+/// The shared intermediate nodes' refcounts are bumped rather than their
+/// sub-trees being deep-copied, so both `parent` and the returned table
+/// keep observing the same kernel mappings as they evolve. The user half of
+/// the returned table is left with no entries.
+///
+/// # Safety
+///
+/// The caller must hold `guard` for at least as long as the snapshot of
+/// `parent`'s root is read, so that concurrent kernel-half updates cannot
+/// race with the entries being copied here.
+#[verus_spec(
+    with Tracked(entry_own): Tracked<&mut EntryOwner<'rcu, C>>
+)]
+pub fn clone_kernel_half<'rcu, C: PageTableConfig, A: InAtomicMode>(
+    parent: &PageTable<C>,
+    guard: &'rcu A,
+) -> PageTable<C> {
+    let new_root = PageTableNodeRef::<'rcu, C>::alloc(guard, C::NR_LEVELS());
+    let new_root_guard = new_root.lock(guard);
+
+    let parent_root = PageTableNodeRef::<'rcu, C>::borrow_paddr(parent.root.start_paddr());
+    let parent_root_guard = parent_root.lock(guard);
+
+    let first_kernel_idx = kernel_pte_index::<C>();
+    let end = nr_subpage_per_huge::<C>();
+    for i in first_kernel_idx..end {
+        let child = PageTableGuard::<'rcu, C>::entry(parent_root_guard, i);
+        if let ChildRef::PageTable(pt) = child.to_ref() {
+            // Share the kernel sub-tree instead of deep-copying it: bump
+            // its refcount so it outlives either address space alone.
+            pt.add_ref();
+            let mut new_entry = PageTableGuard::<'rcu, C>::entry(new_root_guard, i);
+            new_entry.install_node(pt);
+        }
+    }
+
+    // Unlike a sub-tree walk left locked for a `Cursor` to hold onto,
+    // nothing later rediscovers and releases these two root guards: drop
+    // them normally here so the locks are actually released.
+    drop(parent_root_guard);
+    drop(new_root_guard);
+
+    PageTable { root: new_root, _phantom: PhantomData }
+}
+
+/// Forks `parent` into a new address space that shares the kernel half (see
+/// [`clone_kernel_half`]) and copy-on-write shares the user half.
+///
+/// Every present user leaf PTE in `parent` is made read-only (if it was not
+/// already) and its underlying frame's refcount is bumped; the same
+/// read-only PTE, pointing at the same frame, is then installed in the
+/// child. A later write fault in either address space resolves by
+/// duplicating that single frame and remapping just the faulting side
+/// read-write, per the usual copy-on-write contract.
+#[verus_spec(
+    with Tracked(entry_own): Tracked<&mut EntryOwner<'rcu, C>>
+)]
+pub fn fork<'rcu, C: PageTableConfig, A: InAtomicMode>(
+    parent: &PageTable<C>,
+    guard: &'rcu A,
+) -> PageTable<C> {
+    let child = clone_kernel_half::<C, A>(parent, guard);
+
+    let parent_root = PageTableNodeRef::<'rcu, C>::borrow_paddr(parent.root.start_paddr());
+    let parent_root_guard = parent_root.lock(guard);
+    let child_root = PageTableNodeRef::<'rcu, C>::borrow_paddr(child.root.start_paddr());
+    let child_root_guard = child_root.lock(guard);
+
+    let first_kernel_idx = kernel_pte_index::<C>();
+    dfs_cow_share::<C, A>(guard, parent_root_guard, child_root_guard, 0, first_kernel_idx);
+
+    drop(parent_root_guard);
+    drop(child_root_guard);
+
+    child
+}
+
+/// Recursively copy-on-write-shares the user-half entries `[start_idx,
+/// end_idx)` of `parent_node` into the corresponding entries of
+/// `child_node`, making every present leaf PTE read-only on both sides.
+#[verus_spec(
+    with Tracked(entry_own): Tracked<&mut EntryOwner<'rcu, C>>
+)]
+fn dfs_cow_share<'rcu, C: PageTableConfig, A: InAtomicMode>(
+    guard: &'rcu A,
+    parent_node: PPtr<PageTableGuard<'rcu, C>>,
+    child_node: PPtr<PageTableGuard<'rcu, C>>,
+    start_idx: usize,
+    end_idx: usize,
+) {
+    for i in start_idx..end_idx {
+        let parent_entry = PageTableGuard::<'rcu, C>::entry(parent_node, i);
+        match parent_entry.to_ref() {
+            ChildRef::Frame(frame, level, prop) => {
+                // Downgrade to read-only on both sides and share the frame.
+                // `clone_ref` already bumps the refcount once, for the
+                // reference reinstalled in the parent's slot; the child's
+                // slot takes over the reference `frame` already held, so
+                // the net effect is the required "parent keeps 1, child
+                // gains 1" -- an extra `add_ref()` here would
+                // over-count and leak a reference on every fork.
+                let ro_prop = prop.make_read_only();
+                let mut parent_entry = PageTableGuard::<'rcu, C>::entry(parent_node, i);
+                parent_entry.install_frame(frame.clone_ref(), ro_prop);
+                let mut child_entry = PageTableGuard::<'rcu, C>::entry(child_node, i);
+                child_entry.install_frame(frame, ro_prop);
+            }
+            ChildRef::PageTable(pt) => {
+                let parent_child = pt.lock(guard);
+                // `pt` already refers to the child node itself, so its
+                // `level()` is already the level the mirrored node must be
+                // allocated at -- no further decrement.
+                let new_child_node = PageTableNodeRef::<'rcu, C>::alloc(guard, pt.level());
+                let new_child_guard = new_child_node.lock(guard);
+                let nr_entries = nr_subpage_per_huge::<C>();
+                dfs_cow_share::<C, A>(guard, parent_child, new_child_guard, 0, nr_entries);
+
+                let mut child_entry = PageTableGuard::<'rcu, C>::entry(child_node, i);
+                child_entry.install_node(new_child_node);
+
+                drop(parent_child);
+                drop(new_child_guard);
+            }
+            ChildRef::None => {}
+        }
+    }
+}
+
+} // verus!
@@ -39,6 +39,18 @@ pub fn lock_range<'rcu, C: PageTableConfig, A: InAtomicMode>(
     guard: &'rcu A,
     va: &Range<Vaddr>,
 ) -> Cursor<'rcu, C, A> {
+    // Reject a genuinely non-canonical range outright: canonicalization is
+    // only for stripping the bits a tagged pointer is *allowed* to vary
+    // in, not for silently "fixing" an address that is malformed even
+    // after the tag is stripped.
+    debug_assert!(is_canonical_va::<C>(va.start) && is_canonical_va::<C>(va.end));
+
+    // Strip any ignored tag bits (e.g. ARM top-byte-ignore, RISC-V pointer
+    // masking) before the range ever reaches the page-table walk, so a
+    // range expressed with tagged pointers maps to the same sub-tree as its
+    // untagged form.
+    let va = &(C::canonicalize_va(va.start)..C::canonicalize_va(va.end));
+
     // The re-try loop of finding the sub-tree root.
     //
     // If we locked a stray node, we need to re-try. Otherwise, although
@@ -62,7 +74,10 @@ pub fn lock_range<'rcu, C: PageTableConfig, A: InAtomicMode>(
     let cur_node_va = align_down(va.start, page_size(guard_level + 1));
     dfs_acquire_lock(guard, subtree_root, cur_node_va, va.clone());
 
-    let mut path = [None, None, None, None];
+    // `path` is sized to `MAX_NR_LEVELS` so that deep paging modes such as
+    // RISC-V Sv57 (5 levels) fit alongside the shallower ones (e.g. Sv32's
+    // 2 levels).
+    let mut path = [None, None, None, None, None];
     path[guard_level as usize - 1] = Some(subtree_root);
 
     Cursor::<'rcu, C, A> {
@@ -76,26 +91,28 @@ pub fn lock_range<'rcu, C: PageTableConfig, A: InAtomicMode>(
     }
 }
 
-#[verifier::external_body]
-pub fn unlock_range<C: PageTableConfig, A: InAtomicMode>(cursor: &mut Cursor<'_, C, A>) {
-    unimplemented!()
-/*    let end = cursor.guard_level as usize - 1;
-    for i in (0..end) {
+#[verus_spec(
+    with Tracked(entry_own): Tracked<EntryOwner<C>>
+)]
+pub fn unlock_range<'rcu, C: PageTableConfig, A: InAtomicMode>(cursor: &mut Cursor<'rcu, C, A>) {
+    let end = cursor.guard_level as usize - 1;
+    for i in 0..end {
         if let Some(guard) = cursor.path[end - i].take() {
             let _ = ManuallyDrop::new(guard);
         }
     }
     let guard_node = cursor.path[cursor.guard_level as usize - 1].take().unwrap();
-    let cur_node_va = cursor.barrier_va.start / page_size(cursor.guard_level + 1)
-        * page_size(cursor.guard_level + 1);
+    let cur_node_va = align_down(cursor.barrier_va.start, page_size(cursor.guard_level + 1));
 
     // SAFETY: A cursor maintains that its corresponding sub-tree is locked.
-    dfs_release_lock(
-        cursor.rcu_guard,
-        guard_node,
-        cur_node_va,
-        cursor.barrier_va.clone(),
-    );*/
+    unsafe {
+        dfs_release_lock(
+            cursor.rcu_guard,
+            guard_node,
+            cur_node_va,
+            cursor.barrier_va.clone(),
+        );
+    }
 }
 
 /// Finds and locks an intermediate page table node that covers the range.
@@ -107,6 +124,9 @@ pub fn unlock_range<C: PageTableConfig, A: InAtomicMode>(cursor: &mut Cursor<'_,
 /// If this function founds that a locked node is stray (because of racing with
 /// page table recycling), it will return `None`. The caller should retry in
 /// this case to lock the proper node.
+///
+/// `va` must already be canonical (see [`PagingConstsTrait::canonicalize_va`]);
+/// [`lock_range`] is responsible for canonicalizing it before calling here.
 #[verus_spec(
     with Tracked(entry_own) : Tracked<&mut EntryOwner<'rcu, C>>
 )]
@@ -336,7 +356,7 @@ pub fn dfs_mark_stray_and_unlock<'a, C: PageTableConfig, A: InAtomicMode>(
 }
 
 #[verifier::external_body]
-fn dfs_get_idx_range<C: PagingConstsTrait>(
+pub(super) fn dfs_get_idx_range<C: PagingConstsTrait>(
     cur_node_level: PagingLevel,
     cur_node_va: Vaddr,
     va_range: &Range<Vaddr>,
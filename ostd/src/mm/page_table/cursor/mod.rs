@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MPL-2.0
+//! The page table cursor for mapping over the page table.
+mod access;
+mod fork;
+mod locking;
+mod split;
+
+use core::{marker::PhantomData, mem::ManuallyDrop, ops::Range};
+
+pub use access::{sample_working_set, AccessRegion};
+pub use fork::{clone_kernel_half, fork};
+pub use locking::{lock_range, unlock_range};
+use locking::dfs_get_idx_range;
+pub use split::split_huge_page;
+
+use vstd::prelude::*;
+use vstd::simple_pptr::*;
+
+use crate::mm::{
+    page_table::{
+        page_size, ChildRef, PageTableConfig, PageTableEntryTrait, PageTableGuard,
+        PagingConstsTrait, PagingLevel,
+    },
+    Vaddr,
+};
+
+use aster_common::prelude::*;
+use aster_common::prelude::page_table::*;
+
+verus! {
+
+/// The cursor for traversal over the page table.
+///
+/// A cursor locks a sub-tree of the page table that covers [`Self::barrier_va`]
+/// (see [`lock_range`]), and maps pages over that locked range with
+/// [`Self::map_range`]. It has no unmapping or querying operations.
+pub struct Cursor<'rcu, C: PageTableConfig, A: InAtomicMode> {
+    pub(super) path: [Option<PPtr<PageTableGuard<'rcu, C>>>; 5],
+    pub(super) rcu_guard: &'rcu A,
+    pub(super) level: PagingLevel,
+    pub(super) guard_level: PagingLevel,
+    pub(super) va: Vaddr,
+    pub(super) barrier_va: Range<Vaddr>,
+    pub(super) _phantom: PhantomData<C>,
+}
+
+impl<'rcu, C: PageTableConfig, A: InAtomicMode> Cursor<'rcu, C, A> {
+    /// Maps a whole `va` range to consecutive leaf/huge PTEs at `level`,
+    /// walking the already-locked sub-tree in one pass.
+    ///
+    /// `alloc_page` is invoked lazily, once per slot that is newly
+    /// populated along the way: once for every intermediate node that must
+    /// be created while descending to `level`, and once more for the
+    /// leaf/huge PTE's own backing frame. This keeps the mapping logic
+    /// allocator-agnostic: the caller decides how and from where frames are
+    /// allocated. A slot that is already occupied (by either a page table
+    /// node or an existing leaf/huge mapping) is left untouched -- the
+    /// caller must unmap it first.
+    ///
+    /// The caller must have locked `va` into this cursor's range (see
+    /// [`lock_range`]), and `va` must be a subset of [`Self::barrier_va`].
+    #[verus_spec(
+        with Tracked(entry_own): Tracked<&mut EntryOwner<'rcu, C>>
+    )]
+    pub fn map_range(
+        &mut self,
+        va: Range<Vaddr>,
+        level: PagingLevel,
+        prop: PageProperty,
+        alloc_page: &mut impl FnMut() -> Frame<C::E>,
+    ) {
+        let root = self.path[self.guard_level as usize - 1].unwrap();
+        let root_va = align_down(self.barrier_va.start, page_size(self.guard_level + 1));
+        dfs_map_range::<C, A>(self.rcu_guard, root, root_va, va, level, prop, alloc_page);
+    }
+}
+
+/// Recursively walks the locked sub-tree rooted at `cur_node`, installing
+/// PTEs for `va_range` at `target_level`.
+///
+/// `cur_node_va` must be the virtual address of `cur_node`, and `va_range`
+/// must be within the range covered by `cur_node`. Intermediate nodes that
+/// are missing on the way down to `target_level`, and the leaf/huge PTE
+/// finally installed at `target_level`, are all backed by frames obtained
+/// from `alloc_page`. A slot that is already occupied is left as-is.
+#[verus_spec(
+    with Tracked(entry_own): Tracked<&mut EntryOwner<'rcu, C>>
+)]
+fn dfs_map_range<'rcu, C: PageTableConfig, A: InAtomicMode>(
+    guard: &'rcu A,
+    cur_node: PPtr<PageTableGuard<'rcu, C>>,
+    cur_node_va: Vaddr,
+    va_range: Range<Vaddr>,
+    target_level: PagingLevel,
+    prop: PageProperty,
+    alloc_page: &mut impl FnMut() -> Frame<C::E>,
+) {
+    let cur_guard = cur_node.borrow(Tracked(entry_own.guard_perm.borrow()));
+    let cur_level = cur_guard.level();
+
+    let idx_range = dfs_get_idx_range::<C>(cur_level, cur_node_va, &va_range);
+    for i in idx_range {
+        let child_node_va = cur_node_va + i * page_size(cur_level);
+        let child_node_va_end = child_node_va + page_size(cur_level);
+        let child_va_start = va_range.start.max(child_node_va);
+        let child_va_end = va_range.end.min(child_node_va_end);
+
+        let mut cur_entry = PageTableGuard::<'rcu, C>::entry(cur_node, i);
+
+        if cur_level == target_level {
+            // The slot at this index is exactly at `target_level`:
+            // install a fresh leaf/huge PTE instead of descending further,
+            // unless something is mapped there already -- the caller must
+            // unmap it explicitly rather than have it silently clobbered.
+            if cur_entry.is_none() {
+                cur_entry.install_frame(alloc_page(), prop);
+            }
+            continue;
+        }
+
+        // Descend, allocating the intermediate node lazily (from the
+        // caller's `alloc_page`) if it is absent.
+        let child_guard = if cur_entry.is_none() {
+            cur_entry.alloc_if_none_with(guard, alloc_page()).unwrap()
+        } else {
+            match cur_entry.to_ref() {
+                ChildRef::PageTable(pt) => pt.lock(guard),
+                // A huge page already occupies this entry: nothing finer to
+                // map underneath it without first splitting it.
+                ChildRef::Frame(_, _, _) | ChildRef::None => continue,
+            }
+        };
+
+        dfs_map_range::<C, A>(
+            guard,
+            child_guard,
+            child_node_va,
+            child_va_start..child_va_end,
+            target_level,
+            prop,
+            alloc_page,
+        );
+        let _ = ManuallyDrop::new(child_guard);
+    }
+}
+
+} // verus!
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Concurrent huge-page splitting (demotion).
+use core::{mem::ManuallyDrop, sync::atomic::Ordering};
+
+use vstd::prelude::*;
+use vstd::simple_pptr::*;
+
+use crate::mm::{
+    nr_subpage_per_huge,
+    page_table::{
+        page_size, ChildRef, PageTableConfig, PageTableEntryTrait, PageTableGuard,
+        PagingConstsTrait, PagingLevel,
+    },
+};
+
+use aster_common::prelude::*;
+use aster_common::prelude::page_table::*;
+
+verus! {
+
+/// Splits (demotes) the huge page mapped by the last-level PTE at index
+/// `idx` of `cur_node` into `nr_subpage_per_huge::<C>()` leaf PTEs at the
+/// level below.
+///
+/// `cur_node` must already be locked by the caller, and the PTE at `idx`
+/// must be present, last (i.e. a leaf/huge mapping), and at a level above 1.
+/// If the entry is absent or already a page table node, this is a no-op.
+///
+/// This is the inverse of huge-page promotion, and is needed to give a
+/// sub-range of a huge mapping its own `PageProperty` (e.g. for an
+/// `mprotect`-style call).
+///
+/// # Concurrency
+///
+/// The fresh level `L - 1` node is fully populated -- every one of its
+/// `nr_subpage_per_huge::<C>()` entries is written -- before it is
+/// published. The PTE at `idx` that replaces the huge mapping is then
+/// stored with `Release` ordering, so that a concurrent `load_pte` with
+/// `Acquire` (as in `try_traverse_and_lock_subtree_root`) can never observe
+/// a half-built node. The huge frame's refcount is transferred to the sum
+/// of the refcounts of the newly created child frames, so the total
+/// accounting of mapped pages is unchanged by the split.
+#[verus_spec(
+    with Tracked(entry_own): Tracked<&mut EntryOwner<'rcu, C>>
+)]
+pub fn split_huge_page<'rcu, C: PageTableConfig, A: InAtomicMode>(
+    guard: &'rcu A,
+    cur_node: PPtr<PageTableGuard<'rcu, C>>,
+    idx: usize,
+) {
+    let mut cur_entry = PageTableGuard::<'rcu, C>::entry(cur_node, idx);
+    let (huge_frame, level, prop) = match cur_entry.to_ref() {
+        ChildRef::Frame(frame, level, prop) => (frame, level, prop),
+        ChildRef::PageTable(_) | ChildRef::None => return,
+    };
+
+    if level <= 1 {
+        // Already the smallest page size; there is nothing to split.
+        return;
+    }
+
+    let child_level = level - 1;
+    let mut child_node = PageTableGuard::<'rcu, C>::alloc(guard, child_level);
+
+    let nr_children = nr_subpage_per_huge::<C>();
+    let base_paddr = huge_frame.start_paddr();
+    for i in 0..nr_children {
+        let sub_paddr = base_paddr + i * page_size(child_level);
+        // `split_one` hands out one of the huge frame's refcounts per child,
+        // so the sum of the children's refcounts equals the original.
+        let sub_frame = huge_frame.split_one(sub_paddr);
+        let mut sub_entry = PageTableGuard::<'rcu, C>::entry(child_node, i);
+        sub_entry.install_frame(sub_frame, prop);
+    }
+
+    // Publish the replacement PTE with `Release` ordering only after every
+    // child entry above has been written. `cur_entry` was already consumed
+    // by `to_ref()` above, so re-fetch a fresh handle to the same slot
+    // first (matching how `fork.rs`'s CoW path re-fetches `parent_entry`
+    // after its own `to_ref()` for the same reason).
+    let mut cur_entry = PageTableGuard::<'rcu, C>::entry(cur_node, idx);
+    cur_entry.replace_with_node(child_node, Ordering::Release);
+    let _ = ManuallyDrop::new(child_node);
+}
+
+} // verus!